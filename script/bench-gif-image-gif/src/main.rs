@@ -0,0 +1,79 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// This program exercises the Rust GIF decoder at
+// https://github.com/image-rs/image-gif
+//
+// It supersedes the old ../bench-gif-dot-rs benchmark, which targeted
+// https://github.com/Geal/gif.rs. As of 2018-01-27, that library's Decoder
+// was incomplete enough that the benchmark had to hard-code the color
+// table's byte offset and the first graphic block's byte offset instead of
+// parsing the logical screen descriptor itself, and it only ever decoded a
+// table-based image's first frame. image-gif is actively maintained, parses
+// the whole GIF stream (so there are no magic offsets here), and supports
+// multi-frame animations, so this benchmark decodes every frame and reports
+// throughput across the whole animation, not just its first frame.
+//
+// To run this program, do "cargo run --release" from the parent directory
+// (the directory containing the Cargo.toml file).
+
+extern crate gif;
+
+use std::time::Instant;
+
+const REPS: u32 = 50;
+
+fn main() {
+    let src = include_bytes!("../../../test/data/harvesters.gif");
+
+    let mut total_frames = 0u64;
+    let mut total_pixels = 0u64;
+
+    let start = Instant::now();
+    for _ in 0..REPS {
+        let (num_frames, num_pixels) = decode(src);
+        total_frames += num_frames;
+        total_pixels += num_pixels;
+    }
+    let elapsed = start.elapsed();
+
+    let elapsed_nanos = elapsed.as_secs() * 1_000_000_000 + (elapsed.subsec_nanos() as u64);
+    let mp_per_s: u64 = total_pixels * 1_000_000 / elapsed_nanos;
+
+    print!(
+        "{} frames/run, {}.{:03} megapixels/second\n",
+        total_frames / (REPS as u64),
+        mp_per_s / 1_000,
+        mp_per_s % 1_000
+    );
+}
+
+// decode reads the logical screen descriptor and global color table
+// straight out of `src` (image-gif's Decoder parses them; there are no
+// hard-coded byte offsets), then decodes every frame of the animation,
+// returning the number of frames and the total number of pixels decoded.
+fn decode(src: &[u8]) -> (u64, u64) {
+    let mut decoder = gif::Decoder::new(src);
+    let mut reader = decoder.read_info().unwrap();
+
+    let mut num_frames = 0u64;
+    let mut num_pixels = 0u64;
+    while let Some(frame) = reader.read_next_frame().unwrap() {
+        num_frames += 1;
+        num_pixels += (frame.width as u64) * (frame.height as u64);
+    }
+    (num_frames, num_pixels)
+}
@@ -0,0 +1,194 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// This program exercises the Rust WebP decoder at
+// https://github.com/jaredforth/webp (the webp crate, bindings to libwebp),
+// restricted to lossy (VP8) images, which is what Wuffs' std/webp (based on
+// its own VP8 decoder) supports.
+//
+// Wuffs' C code doesn't depend on Rust per se, but this program gives some
+// performance data for a specific Rust WebP implementation. The equivalent
+// Wuffs benchmarks (on the same test images) are run via:
+//
+// wuffs bench std/webp
+//
+// To run this program, do "cargo run --release" from the parent directory
+// (the directory containing the Cargo.toml file).
+
+extern crate rustc_version_runtime;
+extern crate webp;
+
+use std::convert::TryInto;
+use std::time::Instant;
+
+const ITERSCALE: u64 = 50;
+const REPS: u64 = 5;
+
+fn main() {
+    let version = rustc_version_runtime::version();
+    print!(
+        "# Rust {}.{}.{}\n",
+        version.major, version.minor, version.patch,
+    );
+    print!("#\n");
+    print!("# The output format, including the \"Benchmark\" prefixes, is compatible with the\n");
+    print!("# https://godoc.org/golang.org/x/perf/cmd/benchstat tool. To install it, first\n");
+    print!("# install Go, then run \"go install golang.org/x/perf/cmd/benchstat\".\n");
+
+    let mut dst0 = vec![0u8; 64 * 1024 * 1024];
+    let mut dst1 = vec![0u8; 64 * 1024 * 1024];
+
+    // The various magic constants below are copied from test/c/std/webp.c
+    for i in 0..(1 + REPS) {
+        bench(
+            "40k_24bpp",
+            &mut dst0[..],
+            &mut dst1[..],
+            include_bytes!("../../../test/data/hat.lossy.webp"),
+            i == 0,       // warm_up
+            90 * 112 * 4, // want_num_bytes = 40_320
+            30,           // iters_unscaled
+        );
+
+        bench(
+            "552k_24bpp",
+            &mut dst0[..],
+            &mut dst1[..],
+            include_bytes!("../../../test/data/hibiscus.primitive.lossy.webp"),
+            i == 0,        // warm_up
+            312 * 442 * 4, // want_num_bytes = 551_616
+            5,             // iters_unscaled
+        );
+
+        bench(
+            "4002k_24bpp",
+            &mut dst0[..],
+            &mut dst1[..],
+            include_bytes!("../../../test/data/harvesters.lossy.webp"),
+            i == 0,         // warm_up
+            1165 * 859 * 4, // want_num_bytes = 4_002_940
+            1,              // iters_unscaled
+        );
+    }
+}
+
+fn bench(
+    name: &str,          // Benchmark name.
+    dst0: &mut [u8],     // Destination buffer #0.
+    dst1: &mut [u8],     // Destination buffer #1.
+    src: &[u8],          // Source data.
+    warm_up: bool,       // Whether this is a warm up rep.
+    want_num_bytes: u64, // Expected num_bytes per iteration.
+    iters_unscaled: u64, // Base number of iterations.
+) {
+    let iters = iters_unscaled * ITERSCALE;
+    let mut total_num_bytes = 0u64;
+
+    let start = Instant::now();
+    for _ in 0..iters {
+        let n = decode(&mut dst0[..], &mut dst1[..], src);
+        if n != want_num_bytes {
+            panic!("num_bytes: got {}, want {}", n, want_num_bytes);
+        }
+        total_num_bytes += n;
+    }
+    let elapsed = start.elapsed();
+
+    let elapsed_nanos = (elapsed.as_secs() * 1_000_000_000) + (elapsed.subsec_nanos() as u64);
+    let kb_per_s: u64 = total_num_bytes * 1_000_000 / elapsed_nanos;
+
+    if warm_up {
+        return;
+    }
+
+    print!(
+        "Benchmarkrust_webp_decode_image_{:16}   {:8}   {:12} ns/op   {:3}.{:03} MB/s\n",
+        name,
+        iters,
+        elapsed_nanos / iters,
+        kb_per_s / 1_000,
+        kb_per_s % 1_000
+    );
+}
+
+// decode returns the number of bytes processed. The webp crate can hand
+// back either RGB (3 bytes/pixel, opaque) or RGBA (4 bytes/pixel) depending
+// on whether the source image carries an alpha channel, so this branches on
+// `WebPImage::is_alpha` instead of assuming RGB24, the way the unified
+// driver's zune-jpeg backend branches on `output_colorspace`.
+fn decode(dst0: &mut [u8], dst1: &mut [u8], src: &[u8]) -> u64 {
+    let decoder = webp::Decoder::new(src);
+    let image = decoder.decode().expect("failed to decode image");
+    let (w, h) = (image.width() as u64, image.height() as u64);
+    let new_size = (w * h * 4) as usize;
+
+    if image.is_alpha() {
+        // Already 4 bytes/pixel; swap R and B in place to get BGRA.
+        dst0[..image.len()].copy_from_slice(&image);
+        rgba_to_bgra(&mut dst0[..new_size]);
+        dst1[..new_size].copy_from_slice(&dst0[..new_size]);
+    } else {
+        let num_bytes = (w * h * 3) as usize;
+        dst0[..image.len()].copy_from_slice(&image);
+        rgb_to_bgra(&dst0[..num_bytes], &mut dst1[..new_size]);
+    }
+    new_size as u64
+}
+
+/// Copy `src` (treated as 3-byte chunks) into `dst`
+/// (treated as 4-byte chunks), filling out `dst` by adding
+/// a `0xff` "alpha value" at the end of each entry.
+///
+/// # Panics:
+///
+/// Will panic if
+///
+/// * The length of `src` is not a multiple of 3.
+/// * The length of `dst` is not a multiple of 4.
+/// * `src` and `dst` do not have the same length in chunks.
+#[inline]
+pub fn rgb_to_bgra(src: &[u8], dst: &mut [u8]) {
+    let nsrc = src.len();
+    let ndst = dst.len();
+    assert_eq!(0, nsrc % 3);
+    assert_eq!(0, ndst % 4);
+    assert_eq!(nsrc, (ndst / 4) * 3);
+    for (s, d) in src.chunks_exact(3).zip(dst.chunks_exact_mut(4)) {
+        let s: &[u8; 3] = s.try_into().unwrap();
+        let d: &mut [u8; 4] = d.try_into().unwrap();
+        // R
+        d[0] = s[2];
+        // G
+        d[1] = s[1];
+        // B
+        d[2] = s[0];
+        // A
+        d[3] = 0xff;
+    }
+}
+
+/// Swaps the R and B channels of `buf` (treated as 4-byte RGBA chunks) in
+/// place, converting it to straight-alpha BGRA.
+///
+/// # Panics: if the length of `buf` is not a multiple of 4.
+#[inline]
+pub fn rgba_to_bgra(buf: &mut [u8]) {
+    assert_eq!(0, buf.len() % 4);
+    for d in buf.chunks_exact_mut(4) {
+        let d: &mut [u8; 4] = d.try_into().unwrap();
+        d.swap(0, 2);
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// A minimal decode entry point for flate2, used only to measure that
+// crate's compiled code size (see --mode=size in ../src/size.rs). It is
+// deliberately not built on top of this program's own Library trait or
+// corpus table: the point is to isolate flate2's own footprint, not this
+// harness's.
+
+use std::io::Read;
+
+fn main() {
+    let src = &include_bytes!("../../../test/data/romeo.txt.gz")[20..550];
+    let mut dst = Vec::new();
+    let mut decoder = flate2::read::DeflateDecoder::new(src);
+    decoder.read_to_end(&mut dst).unwrap();
+    print!("{}\n", dst.len());
+}
@@ -0,0 +1,45 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// A minimal decode entry point for Geal/gif.rs, used only to measure that
+// crate's compiled code size (see --mode=size in ../src/size.rs). See
+// flate2.rs's comment for why this doesn't go through this program's own
+// Library trait.
+
+const COLOR_TABLE_ELEMENT_COUNT: u16 = 256;
+const COLOR_TABLE_OFFSET: usize = 13;
+const GRAPHIC_BLOCK_OFFSET: usize = 782;
+const NUM_BYTES: usize = 1165 * 859 * 3;
+
+fn main() {
+    let src = include_bytes!("../../../test/data/harvesters.gif");
+    let mut dst = vec![0u8; NUM_BYTES];
+
+    let (_, colors) =
+        gif_dot_rs::parser::color_table(&src[COLOR_TABLE_OFFSET..], COLOR_TABLE_ELEMENT_COUNT)
+            .unwrap();
+    let (_, block) = gif_dot_rs::parser::graphic_block(&src[GRAPHIC_BLOCK_OFFSET..]).unwrap();
+    let rendering = match block {
+        gif_dot_rs::parser::Block::GraphicBlock(_, x) => x,
+        _ => panic!("not a graphic block"),
+    };
+    let (code_size, blocks) = match rendering {
+        gif_dot_rs::parser::GraphicRenderingBlock::TableBasedImage(_, x, y) => (x, y),
+        _ => panic!("not a table based image"),
+    };
+    let num_bytes = gif_dot_rs::lzw::decode_lzw(&colors, code_size as usize, blocks, &mut dst).unwrap();
+    print!("{}\n", num_bytes);
+}
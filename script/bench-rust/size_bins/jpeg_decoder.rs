@@ -0,0 +1,27 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// A minimal decode entry point for jpeg-decoder, used only to measure that
+// crate's compiled code size (see --mode=size in ../src/size.rs). See
+// flate2.rs's comment for why this doesn't go through this program's own
+// Library trait.
+
+fn main() {
+    let src = include_bytes!("../../../test/data/bricks-color.jpeg");
+    let mut decoder = jpeg_decoder::Decoder::new(&src[..]);
+    let pixels = decoder.decode().expect("failed to decode image");
+    print!("{}\n", pixels.len());
+}
@@ -0,0 +1,126 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// Command line flag parsing for the unified mimic-decoder benchmark driver.
+//
+// The flags are deliberately parsed by hand instead of pulling in a clap (or
+// similar) dependency: this program's whole point is to measure other
+// crates' decode performance, not to grow its own dependency tree, and
+// `--name=value`/boolean flags are simple enough that hand-rolling them
+// hasn't gotten out of hand even as the flag count has grown.
+
+use crate::corpus::Format;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    /// Benchmark decoding the corpus test images (the default).
+    Decode,
+    /// Benchmark quantizing + encoding a raw BGRA frame.
+    Encode,
+    /// Measure each library's stripped native and gzipped wasm32 code size.
+    Size,
+}
+
+pub struct Args {
+    pub mode: Mode,
+    pub format: Option<Format>,
+    pub library: Option<String>,
+    pub focus: Option<String>,
+    pub iterscale: u64,
+    pub verify: bool,
+    pub premultiply: bool,
+    pub dither: bool,
+    pub sample: bool,
+}
+
+impl Default for Args {
+    fn default() -> Args {
+        Args {
+            mode: Mode::Decode,
+            format: None,
+            library: None,
+            focus: None,
+            iterscale: 1,
+            verify: false,
+            premultiply: false,
+            dither: false,
+            sample: false,
+        }
+    }
+}
+
+/// Parses `--mode=decode|encode|size`, `--format=X`, `--library=X`,
+/// `--focus=X` and `--iterscale=X` flags (in `--name=value` form) out of
+/// `argv`, plus the boolean `--verify`, `--premultiply`, `--dither` and
+/// `--sample` flags. Unrecognized flags are an error; an absent `--format`
+/// or `--library` means "run every format / library".
+pub fn parse_args<I: Iterator<Item = String>>(argv: I) -> Result<Args, String> {
+    let mut args = Args::default();
+    for arg in argv {
+        if let Some(value) = arg.strip_prefix("--mode=") {
+            args.mode = match value {
+                "decode" => Mode::Decode,
+                "encode" => Mode::Encode,
+                "size" => Mode::Size,
+                _ => return Err(format!("unknown --mode {:?}", value)),
+            };
+        } else if let Some(value) = arg.strip_prefix("--format=") {
+            args.format = Some(Format::parse(value)?);
+        } else if let Some(value) = arg.strip_prefix("--library=") {
+            args.library = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--focus=") {
+            args.focus = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--iterscale=") {
+            args.iterscale = value
+                .parse::<u64>()
+                .map_err(|e| format!("--iterscale: {}", e))?;
+        } else if arg == "--verify" {
+            args.verify = true;
+        } else if arg == "--premultiply" {
+            args.premultiply = true;
+        } else if arg == "--dither" {
+            args.dither = true;
+        } else if arg == "--sample" {
+            args.sample = true;
+        } else {
+            return Err(format!("unrecognized argument: {:?}", arg));
+        }
+    }
+    Ok(args)
+}
+
+/// Reports whether `name` matches `pattern`, where `pattern` is a
+/// comma-separated list of glob-like terms, each containing at most one `*`
+/// wildcard. An absent pattern matches everything.
+pub fn matches_focus(pattern: &Option<String>, name: &str) -> bool {
+    let pattern = match pattern {
+        None => return true,
+        Some(p) => p,
+    };
+    pattern.split(',').any(|term| matches_term(term, name))
+}
+
+fn matches_term(term: &str, name: &str) -> bool {
+    match term.find('*') {
+        None => term == name,
+        Some(i) => {
+            let (prefix, suffix) = (&term[..i], &term[i + 1..]);
+            name.len() >= prefix.len() + suffix.len()
+                && name.starts_with(prefix)
+                && name.ends_with(suffix)
+        }
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// This exercises the Rust Deflate decoder at
+// https://github.com/alexcrichton/flate2-rs
+// which is the top result for https://crates.io/search?q=flate&sort=downloads
+
+use std::io::Read;
+
+use crate::corpus::Format;
+use crate::decoders::{DecodeOpts, Library};
+
+pub struct Flate2;
+
+impl Library for Flate2 {
+    fn name(&self) -> &'static str {
+        "flate2"
+    }
+
+    fn format(&self) -> Format {
+        Format::Deflate
+    }
+
+    fn decode(&self, dst: &mut [u8], src: &[u8], _opts: DecodeOpts) -> u64 {
+        let mut num_bytes = 0u64;
+        let mut decoder = flate2::read::DeflateDecoder::new(src);
+        loop {
+            let n = decoder.read(dst).unwrap();
+            if n == 0 {
+                break;
+            }
+            num_bytes += n as u64;
+        }
+        num_bytes
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// This exercises the Rust GIF decoder at
+// https://github.com/image-rs/image-gif
+// which is the top result for https://crates.io/search?q=gif&sort=downloads
+//
+// Both this crate and gif_dot_rs.rs's https://github.com/Geal/gif.rs crate
+// publish under the name "gif", which Cargo can't link into the same binary
+// under the same name (https://github.com/rust-lang/cargo/issues/1311). The
+// Cargo.toml for this program therefore renames this dependency's package
+// key to `image-gif` (`image_gif` once `use`d), so both libraries can live
+// in the one driver binary.
+
+use crate::corpus::Format;
+use crate::decoders::{DecodeOpts, Library};
+use crate::pixel_convert;
+
+pub struct ImageGif;
+
+impl Library for ImageGif {
+    fn name(&self) -> &'static str {
+        "image-gif"
+    }
+
+    fn format(&self) -> Format {
+        Format::Gif
+    }
+
+    fn decode(&self, dst: &mut [u8], src: &[u8], opts: DecodeOpts) -> u64 {
+        let mut num_bytes = 0u64;
+        let mut decoder = image_gif::Decoder::new(src);
+        decoder.set(if opts.bgra {
+            image_gif::ColorOutput::RGBA
+        } else {
+            image_gif::ColorOutput::Indexed
+        });
+
+        let mut reader = decoder.read_info().unwrap();
+        let mut rgba = Vec::new();
+        while let Some(_) = reader.next_frame_info().unwrap() {
+            let frame_size = reader.buffer_size();
+            if opts.bgra {
+                // The RGBA frame and its BGRA conversion are the same size,
+                // so convert via a scratch buffer rather than in dst itself.
+                rgba.resize(frame_size, 0);
+                reader.read_into_buffer(&mut rgba).unwrap();
+                pixel_convert::rgba_to_bgra(&rgba, &mut dst[..frame_size], opts.alpha_mode);
+            } else {
+                reader.read_into_buffer(&mut dst[..frame_size]).unwrap();
+            }
+            num_bytes += frame_size as u64;
+        }
+        num_bytes
+    }
+}
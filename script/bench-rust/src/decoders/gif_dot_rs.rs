@@ -0,0 +1,68 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// This exercises the Rust GIF decoder at https://github.com/Geal/gif.rs
+//
+// As of October 2019, this library doesn't build
+// (https://github.com/Geal/gif.rs/issues/5) and its `Decoder` is incomplete
+// and doesn't expose enough API to parse an arbitrary GIF, so this only
+// supports the "1000k" corpus entry (test/data/harvesters.gif), via the
+// hard-coded offsets below. The corpus table restricts that entry to this
+// library, and every other GIF entry to image-gif, via
+// `CorpusEntry::only_library`, so it's never run against any other GIF test
+// image. See decoders/gif.rs for why this needs a Cargo
+// package rename to coexist with the image-gif based `Library` impl.
+
+use crate::corpus::Format;
+use crate::decoders::{DecodeOpts, Library};
+
+const COLOR_TABLE_ELEMENT_COUNT: u16 = 256;
+const COLOR_TABLE_OFFSET: usize = 13;
+const GRAPHIC_BLOCK_OFFSET: usize = 782;
+
+pub struct GifDotRs;
+
+impl Library for GifDotRs {
+    fn name(&self) -> &'static str {
+        "gif.rs"
+    }
+
+    fn format(&self) -> Format {
+        Format::Gif
+    }
+
+    fn decode(&self, dst: &mut [u8], src: &[u8], _opts: DecodeOpts) -> u64 {
+        let (_, colors) = gif_dot_rs::parser::color_table(
+            &src[COLOR_TABLE_OFFSET..],
+            COLOR_TABLE_ELEMENT_COUNT,
+        )
+        .unwrap();
+
+        let (_, block) = gif_dot_rs::parser::graphic_block(&src[GRAPHIC_BLOCK_OFFSET..]).unwrap();
+
+        let rendering = match block {
+            gif_dot_rs::parser::Block::GraphicBlock(_, x) => x,
+            _ => panic!("not a graphic block"),
+        };
+
+        let (code_size, blocks) = match rendering {
+            gif_dot_rs::parser::GraphicRenderingBlock::TableBasedImage(_, x, y) => (x, y),
+            _ => panic!("not a table based image"),
+        };
+
+        gif_dot_rs::lzw::decode_lzw(&colors, code_size as usize, blocks, dst).unwrap() as u64
+    }
+}
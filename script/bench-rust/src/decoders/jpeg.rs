@@ -0,0 +1,60 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// This exercises the Rust JPEG decoder at
+// https://github.com/image-rs/jpeg-decoder
+// which is a popular result for https://crates.io/search?q=jpeg&sort=downloads
+
+use crate::corpus::Format;
+use crate::decoders::{DecodeOpts, Library};
+use crate::pixel_convert;
+
+pub struct JpegDecoder;
+
+impl Library for JpegDecoder {
+    fn name(&self) -> &'static str {
+        "jpeg-decoder"
+    }
+
+    fn format(&self) -> Format {
+        Format::Jpeg
+    }
+
+    fn decode(&self, dst: &mut [u8], src: &[u8], opts: DecodeOpts) -> u64 {
+        let mut decoder = jpeg_decoder::Decoder::new(src);
+        let pixels = decoder.decode().expect("failed to decode image");
+        let metadata = decoder.info().unwrap();
+        let w = metadata.width as u64;
+        let h = metadata.height as u64;
+        match metadata.pixel_format {
+            jpeg_decoder::PixelFormat::L8 => {
+                // No conversion necessary.
+                dst[..pixels.len()].copy_from_slice(&pixels);
+                w * h
+            }
+            jpeg_decoder::PixelFormat::RGB24 => {
+                pixel_convert::rgb_to_bgra(
+                    &pixels,
+                    &mut dst[..(w * h * 4) as usize],
+                    opts.alpha_mode,
+                );
+                w * h * 4
+            }
+            // Returning 0 should lead to a panic (when want_num_bytes != 0).
+            _ => 0,
+        }
+    }
+}
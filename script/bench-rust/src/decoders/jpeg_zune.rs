@@ -0,0 +1,66 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// This exercises the Rust JPEG decoder at https://github.com/etemesi254/zune-image
+// (the zune-jpeg crate), a second JPEG mimic backend alongside jpeg.rs's
+// jpeg-decoder. Real consumers have historically routed progressive JPEGs
+// to a different backend than baseline/sequential ones (Servo used stb_image
+// specifically for progressive JPEG while keeping another library for
+// baseline), so having two backends selectable via --library lets
+// --format=jpeg benchmarks show per-backend baseline-vs-progressive
+// throughput differences instead of averaging them away.
+
+use crate::corpus::Format;
+use crate::decoders::{DecodeOpts, Library};
+use crate::pixel_convert;
+use zune_core::colorspace::ColorSpace;
+
+pub struct ZuneJpeg;
+
+impl Library for ZuneJpeg {
+    fn name(&self) -> &'static str {
+        "zune-jpeg"
+    }
+
+    fn format(&self) -> Format {
+        Format::Jpeg
+    }
+
+    fn decode(&self, dst: &mut [u8], src: &[u8], opts: DecodeOpts) -> u64 {
+        let mut decoder = zune_jpeg::JpegDecoder::new(src);
+        let pixels = decoder.decode().expect("failed to decode image");
+        let info = decoder.info().unwrap();
+        let w = info.width as u64;
+        let h = info.height as u64;
+        match decoder.output_colorspace().unwrap_or(ColorSpace::RGB) {
+            ColorSpace::Luma => {
+                // No conversion necessary, same as the jpeg-decoder backend.
+                dst[..pixels.len()].copy_from_slice(&pixels);
+                w * h
+            }
+            ColorSpace::RGB => {
+                pixel_convert::rgb_to_bgra(
+                    &pixels,
+                    &mut dst[..(w * h * 4) as usize],
+                    opts.alpha_mode,
+                );
+                w * h * 4
+            }
+            // Returning 0 should lead to a panic (when want_num_bytes != 0).
+            _ => 0,
+        }
+    }
+}
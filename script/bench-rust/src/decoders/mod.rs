@@ -0,0 +1,67 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// The per-library decode implementations, each behind the `Library` trait,
+// so that main.rs's benchmark loop doesn't need to know the details of any
+// particular Rust decoder crate.
+
+pub mod deflate;
+pub mod gif;
+pub mod gif_dot_rs;
+pub mod jpeg;
+pub mod jpeg_zune;
+
+use crate::corpus::Format;
+use crate::pixel_convert::AlphaMode;
+
+/// Options threaded through to every `Library::decode` call. Most libraries
+/// ignore most of these (e.g. Deflate has no pixels at all; JPEG here has no
+/// alpha channel), but a uniform signature keeps the benchmark loop in
+/// main.rs format-agnostic.
+#[derive(Clone, Copy)]
+pub struct DecodeOpts {
+    /// Whether to convert the decoded pixels to BGRA (as opposed to
+    /// whatever layout is cheapest for the library to produce, e.g. palette
+    /// indices for GIF).
+    pub bgra: bool,
+    /// How to handle alpha when converting to BGRA.
+    pub alpha_mode: AlphaMode,
+}
+
+/// A mimic library: one Rust crate that can decode one `Format`.
+pub trait Library {
+    /// The `--library` flag value that selects this implementation.
+    fn name(&self) -> &'static str;
+
+    /// The format that this library decodes.
+    fn format(&self) -> Format;
+
+    /// Decodes `src` into `dst`, returning the number of bytes written.
+    /// `dst` is at least as large as the corpus entry's `want_num_bytes`.
+    fn decode(&self, dst: &mut [u8], src: &[u8], opts: DecodeOpts) -> u64;
+}
+
+/// Returns every known `Library`, in the order that `--library` (when
+/// absent) iterates over them.
+pub fn all() -> Vec<Box<dyn Library>> {
+    vec![
+        Box::new(deflate::Flate2),
+        Box::new(gif::ImageGif),
+        Box::new(gif_dot_rs::GifDotRs),
+        Box::new(jpeg::JpegDecoder),
+        Box::new(jpeg_zune::ZuneJpeg),
+    ]
+}
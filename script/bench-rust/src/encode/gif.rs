@@ -0,0 +1,69 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// Times image-gif's LZW encoder over an already quantized-and-remapped
+// frame, the GIF half of the gifski-style pipeline.
+
+use std::time::{Duration, Instant};
+
+use crate::encode::EncodeEntry;
+
+pub fn bench(entry: &EncodeEntry, dither: bool) {
+    let (palette, indices) = super::bench_quantize(entry, dither);
+
+    let mut flat_palette = Vec::with_capacity(palette.len() * 3);
+    for p in &palette {
+        flat_palette.extend_from_slice(p);
+    }
+
+    let mut out_len = 0usize;
+    let mut elapsed_total = Duration::ZERO;
+    for i in 0..(1 + super::REPS) {
+        let mut out = Vec::new();
+        let start = Instant::now();
+        {
+            let mut encoder = image_gif::Encoder::new(
+                &mut out,
+                entry.width as u16,
+                entry.height as u16,
+                &flat_palette,
+            )
+            .unwrap();
+            let frame = image_gif::Frame {
+                width: entry.width as u16,
+                height: entry.height as u16,
+                buffer: std::borrow::Cow::Borrowed(&indices[..]),
+                ..image_gif::Frame::default()
+            };
+            encoder.write_frame(&frame).unwrap();
+        }
+        let elapsed = start.elapsed();
+        out_len = out.len();
+        if i == 0 {
+            // Warm up rep; don't count its time.
+            continue;
+        }
+        elapsed_total += elapsed;
+    }
+
+    super::report(
+        &super::display_name(entry.name, dither),
+        "gif",
+        elapsed_total,
+        (entry.width * entry.height * 4) as u64,
+        out_len,
+    );
+}
@@ -0,0 +1,161 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// Every other benchmark in this program measures decoding. This module
+// measures the other direction: taking a raw BGRA frame buffer through the
+// same color-quantization + palette-remap + LZW/Deflate encode pipeline
+// that tools like gifski build on top of the image-gif and png crates, and
+// reporting the resulting throughput and output size. The equivalent Wuffs
+// benchmarks (on the same test images) are run via:
+//
+// wuffs bench -mimic std/gif std/png
+
+pub mod gif;
+pub mod png;
+
+use std::time::{Duration, Instant};
+
+use crate::decoders::{DecodeOpts, Library};
+use crate::pixel_convert::AlphaMode;
+use crate::quantize;
+
+/// Number of timed reps per benchmark, matching the decode benchmarks'
+/// `REPS` (plus one untimed warm-up rep).
+pub const REPS: u64 = 5;
+
+/// One encode-corpus entry: a name and a raw BGRA frame buffer (obtained by
+/// decoding an existing entry from the decode corpus, rather than checking
+/// in a second copy of the same test image in a different format).
+pub struct EncodeEntry {
+    pub name: &'static str,
+    pub width: usize,
+    pub height: usize,
+    pub bgra: fn() -> Vec<u8>,
+}
+
+pub fn corpus() -> Vec<EncodeEntry> {
+    vec![
+        EncodeEntry {
+            name: "10k",
+            width: 90,
+            height: 112,
+            bgra: || raw_bgra(include_bytes!("../../../test/data/hat.gif"), 90, 112),
+        },
+        EncodeEntry {
+            name: "100k_realistic",
+            width: 312,
+            height: 442,
+            bgra: || {
+                raw_bgra(
+                    include_bytes!("../../../test/data/hibiscus.regular.gif"),
+                    312,
+                    442,
+                )
+            },
+        },
+    ]
+}
+
+/// Decodes `src` (a GIF) to a raw BGRA frame, reusing the decoders::gif
+/// library rather than checking in a second, pre-decoded copy of the same
+/// test image.
+fn raw_bgra(src: &[u8], width: usize, height: usize) -> Vec<u8> {
+    let mut dst = vec![0u8; width * height * 4];
+    let opts = DecodeOpts {
+        bgra: true,
+        alpha_mode: AlphaMode::Straight,
+    };
+    let n = crate::decoders::gif::ImageGif.decode(&mut dst, src, opts);
+    dst.truncate(n as usize);
+    dst
+}
+
+/// Runs the shared quantize + remap stage once, returning (palette,
+/// indices). This is the pure, unmeasured version; `bench_quantize` is what
+/// actually times it.
+fn quantize_and_remap(
+    bgra: &[u8],
+    width: usize,
+    height: usize,
+    dither: bool,
+) -> (Vec<[u8; 3]>, Vec<u8>) {
+    let palette = quantize::build_palette(bgra, 256);
+    let indices = quantize::remap(bgra, width, height, &palette, dither);
+    (palette, indices)
+}
+
+/// Times the shared quantize + remap stage over `REPS` reps (plus one
+/// untimed warm-up rep, matching every decode benchmark), printing its own
+/// `Benchmarkrust_encode_quantize_...` line so that "how expensive is
+/// quantization" and "how expensive is the bitstream encode" aren't
+/// conflated. Returns the last rep's (palette, indices) so the caller's own
+/// format-specific encode stage doesn't have to quantize a second time.
+pub fn bench_quantize(entry: &EncodeEntry, dither: bool) -> (Vec<[u8; 3]>, Vec<u8>) {
+    let bgra = (entry.bgra)();
+
+    let mut result = quantize_and_remap(&bgra, entry.width, entry.height, dither);
+    let mut elapsed_total = Duration::ZERO;
+    for i in 0..(1 + REPS) {
+        let start = Instant::now();
+        result = quantize_and_remap(&bgra, entry.width, entry.height, dither);
+        let elapsed = start.elapsed();
+        if i == 0 {
+            // Warm up rep; don't count its time.
+            continue;
+        }
+        elapsed_total += elapsed;
+    }
+
+    report(
+        &display_name(entry.name, dither),
+        "quantize",
+        elapsed_total,
+        (entry.width * entry.height * 4) as u64,
+        result.1.len(),
+    );
+    result
+}
+
+/// The benchmark name for `entry`, with a `_dither` suffix when dithering is
+/// enabled.
+pub fn display_name(name: &str, dither: bool) -> String {
+    if dither {
+        format!("{}_dither", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Prints one `Benchmark...` line for a stage that ran `REPS` timed reps
+/// (plus one untimed warm-up rep): `elapsed_total` is the summed duration of
+/// those `REPS` reps, and `num_bytes_per_rep` is the input size processed by
+/// each one.
+pub fn report(name: &str, stage: &str, elapsed_total: Duration, num_bytes_per_rep: u64, out_bytes: usize) {
+    let elapsed_nanos =
+        ((elapsed_total.as_secs() * 1_000_000_000) + (elapsed_total.subsec_nanos() as u64)).max(1);
+    let total_bytes = num_bytes_per_rep * REPS;
+    let kb_per_s: u64 = total_bytes * 1_000_000 / elapsed_nanos;
+    print!(
+        "Benchmarkrust_encode_{}_{:16}   {:8}   {:12} ns/op   {:3}.{:03} MB/s   {:8} bytes/op\n",
+        stage,
+        name,
+        REPS,
+        elapsed_nanos / REPS,
+        kb_per_s / 1_000,
+        kb_per_s % 1_000,
+        out_bytes,
+    );
+}
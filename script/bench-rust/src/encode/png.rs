@@ -0,0 +1,62 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// Times image-png's Deflate encoder over an already quantized-and-remapped
+// frame, written out as an indexed (palette) PNG. This is the PNG half of
+// the gifski-style quantize-then-compress pipeline.
+
+use std::time::{Duration, Instant};
+
+use crate::encode::EncodeEntry;
+
+pub fn bench(entry: &EncodeEntry, dither: bool) {
+    let (palette, indices) = super::bench_quantize(entry, dither);
+
+    let mut flat_palette = Vec::with_capacity(palette.len() * 3);
+    for p in &palette {
+        flat_palette.extend_from_slice(p);
+    }
+
+    let mut out_len = 0usize;
+    let mut elapsed_total = Duration::ZERO;
+    for i in 0..(1 + super::REPS) {
+        let mut out = Vec::new();
+        let start = Instant::now();
+        {
+            let mut encoder = png::Encoder::new(&mut out, entry.width as u32, entry.height as u32);
+            encoder.set_color(png::ColorType::Indexed);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_palette(flat_palette.clone());
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&indices).unwrap();
+        }
+        let elapsed = start.elapsed();
+        out_len = out.len();
+        if i == 0 {
+            // Warm up rep; don't count its time.
+            continue;
+        }
+        elapsed_total += elapsed;
+    }
+
+    super::report(
+        &super::display_name(entry.name, dither),
+        "png",
+        elapsed_total,
+        (entry.width * entry.height * 4) as u64,
+        out_len,
+    );
+}
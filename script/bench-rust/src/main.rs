@@ -0,0 +1,291 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// This program exercises several Rust mimic decoders (Deflate, GIF, JPEG)
+// against the same test corpus that Wuffs itself benchmarks. It replaces
+// what used to be four near-identical programs (bench-rust-deflate,
+// bench-rust-gif, bench-rust-gif-dot-rs and bench-rust-jpeg), each of which
+// hard-coded its own `main`/`bench`/`decode` scaffolding, its own slice of
+// the test corpus and its own `ITERSCALE`/`REPS` constants. This mirrors how
+// the Rust project itself eventually pulled its scattered ad-hoc benches
+// into one organized harness (the `benches` workspace member driven by a
+// single `cargo bench` entry point).
+//
+// Wuffs' C code doesn't depend on Rust per se, but this program gives some
+// performance data for specific Rust decoder implementations. The
+// equivalent Wuffs benchmarks (on the same test images) are run via:
+//
+// wuffs bench std/deflate std/gif std/jpeg
+//
+// To run this program, do "cargo run --release" from the parent directory
+// (the directory containing the Cargo.toml file). Flags:
+//
+//   --format=deflate|gif|jpeg   run only one format (default: all)
+//   --library=NAME              run only one library, e.g. flate2 (default: all)
+//   --focus=GLOB[,GLOB...]      run only benchmark names matching a glob
+//   --iterscale=N               multiply every corpus entry's iteration count
+//   --verify                    cross-check decoded content, not just its length
+//   --premultiply               convert "_bgra" entries to premultiplied alpha
+//   --mode=decode|encode|size   benchmark decoding (default), encoding, or
+//                               measure each library's compiled code size
+//   --dither                    (encode mode) Floyd-Steinberg dither the quantized output
+//   --sample                    (decode mode) report each rep's ns/op plus a
+//                               median/MAD summary, instead of one aggregate
+//                               ns/op number
+
+mod cli;
+mod corpus;
+mod decoders;
+mod encode;
+mod pixel_convert;
+mod quantize;
+mod size;
+mod stats;
+mod verify;
+
+use std::time::Instant;
+
+use cli::Mode;
+use decoders::{DecodeOpts, Library};
+use pixel_convert::AlphaMode;
+
+const REPS: u64 = 5;
+
+fn main() {
+    let version = rustc_version_runtime::version();
+    print!(
+        "# Rust {}.{}.{}\n",
+        version.major, version.minor, version.patch,
+    );
+    print!("#\n");
+    print!("# The output format, including the \"Benchmark\" prefixes, is compatible with the\n");
+    print!("# https://godoc.org/golang.org/x/perf/cmd/benchstat tool. To install it, first\n");
+    print!("# install Go, then run \"go install golang.org/x/perf/cmd/benchstat\".\n");
+
+    let args = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprint!("{}\n", message);
+            std::process::exit(1);
+        }
+    };
+
+    match args.mode {
+        Mode::Decode => run_decode(&args),
+        Mode::Encode => run_encode(&args),
+        Mode::Size => size::run(&args),
+    }
+}
+
+fn run_decode(args: &cli::Args) {
+    let mut dst = vec![0u8; 64 * 1024 * 1024];
+
+    for library in decoders::all() {
+        if let Some(format) = args.format {
+            if library.format() != format {
+                continue;
+            }
+        }
+        if let Some(want) = &args.library {
+            if library.name() != want {
+                continue;
+            }
+        }
+        run(library.as_ref(), args, &mut dst);
+    }
+}
+
+fn run_encode(args: &cli::Args) {
+    for entry in encode::corpus() {
+        if !cli::matches_focus(&args.focus, entry.name) {
+            continue;
+        }
+        encode::gif::bench(&entry, args.dither);
+        encode::png::bench(&entry, args.dither);
+    }
+}
+
+fn run(library: &dyn Library, args: &cli::Args, dst: &mut [u8]) {
+    for entry in corpus::entries(library.format()) {
+        if let Some(only) = entry.only_library {
+            if only != library.name() {
+                continue;
+            }
+        }
+        if !cli::matches_focus(&args.focus, entry.name) {
+            continue;
+        }
+        bench(library, args, entry, dst);
+    }
+}
+
+fn bench(library: &dyn Library, args: &cli::Args, entry: &corpus::CorpusEntry, dst: &mut [u8]) {
+    let src = (entry.data)();
+    let iters = entry.iters_unscaled * args.iterscale;
+    let opts = DecodeOpts {
+        bgra: entry.name.ends_with("_bgra"),
+        alpha_mode: if args.premultiply {
+            AlphaMode::Premultiplied
+        } else {
+            AlphaMode::Straight
+        },
+    };
+
+    if args.verify {
+        match &entry.golden {
+            Some(golden) => {
+                let n = library.decode(dst, src, opts);
+                crate::verify::check(
+                    library.format().name(),
+                    library.name(),
+                    entry.name,
+                    &dst[..n as usize],
+                    golden,
+                );
+            }
+            // See corpus.rs's CorpusEntry.golden doc comment: not every
+            // entry has a golden yet, so say so explicitly instead of
+            // --verify silently checking nothing for this one.
+            None => eprint!(
+                "{}/{}/{}: --verify: no golden for this corpus entry yet, skipping content check\n",
+                library.format().name(),
+                library.name(),
+                entry.name,
+            ),
+        }
+    }
+
+    if args.sample {
+        bench_samples(library, entry, dst, src, iters, opts);
+        return;
+    }
+
+    let mut total_num_bytes = 0u64;
+    let mut start = Instant::now();
+    for i in 0..(1 + REPS) {
+        let mut rep_num_bytes = 0u64;
+        for _ in 0..iters {
+            rep_num_bytes += decode_checked(library, entry, dst, src, opts);
+        }
+        if i == 0 {
+            // Warm up rep; don't count its time.
+            start = Instant::now();
+            continue;
+        }
+        total_num_bytes += rep_num_bytes;
+    }
+    let elapsed = start.elapsed();
+
+    let elapsed_nanos = (elapsed.as_secs() * 1_000_000_000) + (elapsed.subsec_nanos() as u64);
+    let kb_per_s: u64 = total_num_bytes * 1_000_000 / elapsed_nanos;
+    let total_iters = iters * REPS;
+
+    print!(
+        "Benchmarkrust_{}_decode_{}_{}{:16}   {:8}   {:12} ns/op   {:3}.{:03} MB/s\n",
+        library.format().name(),
+        library.name(),
+        bucket_prefix(entry),
+        entry.name,
+        total_iters,
+        elapsed_nanos / total_iters,
+        kb_per_s / 1_000,
+        kb_per_s % 1_000
+    );
+}
+
+/// Decodes `src` once, panicking if the decoded byte count doesn't match
+/// `entry.want_num_bytes`.
+fn decode_checked(
+    library: &dyn Library,
+    entry: &corpus::CorpusEntry,
+    dst: &mut [u8],
+    src: &[u8],
+    opts: DecodeOpts,
+) -> u64 {
+    let n = library.decode(dst, src, opts);
+    if n != entry.want_num_bytes {
+        panic!(
+            "{}/{}/{}: num_bytes: got {}, want {}",
+            library.format().name(),
+            library.name(),
+            entry.name,
+            n,
+            entry.want_num_bytes
+        );
+    }
+    n
+}
+
+fn bucket_prefix(entry: &corpus::CorpusEntry) -> String {
+    match entry.bucket {
+        Some(bucket) => format!("{}_", bucket),
+        None => String::new(),
+    }
+}
+
+/// `--sample` mode: times each of `REPS` reps individually (after one
+/// untimed warm-up rep), printing every rep's own `Benchmark...` line so
+/// that benchstat sees them as independent samples, then a summary line
+/// with the median ns/op and its median absolute deviation (MAD).
+fn bench_samples(
+    library: &dyn Library,
+    entry: &corpus::CorpusEntry,
+    dst: &mut [u8],
+    src: &[u8],
+    iters: u64,
+    opts: DecodeOpts,
+) {
+    for _ in 0..iters {
+        decode_checked(library, entry, dst, src, opts);
+    }
+
+    let mut ns_per_iter = Vec::with_capacity(REPS as usize);
+    for _ in 0..REPS {
+        let start = Instant::now();
+        for _ in 0..iters {
+            decode_checked(library, entry, dst, src, opts);
+        }
+        let elapsed = start.elapsed();
+        let elapsed_nanos = (elapsed.as_secs() * 1_000_000_000) + (elapsed.subsec_nanos() as u64);
+        ns_per_iter.push(elapsed_nanos / iters);
+    }
+
+    let bucket = bucket_prefix(entry);
+    for ns in &ns_per_iter {
+        print!(
+            "Benchmarkrust_{}_decode_{}_{}{:16}   {:8}   {:12} ns/op\n",
+            library.format().name(),
+            library.name(),
+            bucket,
+            entry.name,
+            iters,
+            ns,
+        );
+    }
+
+    let median = stats::median(&mut ns_per_iter);
+    let mad = stats::median_absolute_deviation(&ns_per_iter, median);
+    print!(
+        "Benchmarkrust_{}_decode_{}_{}{:16}   {:8}   {:12} ± {:6} ns/op\n",
+        library.format().name(),
+        library.name(),
+        bucket,
+        entry.name,
+        iters,
+        median,
+        mad,
+    );
+}
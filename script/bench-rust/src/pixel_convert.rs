@@ -0,0 +1,128 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// Shared pixel-format conversion to BGRA, the layout every benchmark
+// program converts its decoded pixels to before reporting throughput. This
+// used to be a single hard-coded RGB24->BGRA converter living in the JPEG
+// program, while the GIF program relied on the gif crate's own RGBA
+// expansion and the L8 path did no conversion at all, so the benchmarks
+// weren't measuring the same output layout across libraries.
+//
+// Browser image pipelines premultiply GIF/PNG alpha before compositing, so
+// `AlphaMode::Premultiplied` lets `--premultiply` benchmarks reflect the
+// full decode-to-display cost that those consumers pay, not just raw
+// channel copying.
+
+use std::convert::TryInto;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AlphaMode {
+    /// The alpha channel is copied as-is; R/G/B are untouched.
+    Straight,
+    /// Each of R/G/B is scaled by `a/255` (rounded), matching what a
+    /// compositor expects of premultiplied-alpha BGRA.
+    Premultiplied,
+}
+
+/// Copies `src` (treated as 1-byte grayscale samples) into `dst` (treated as
+/// 4-byte BGRA chunks), setting alpha to `0xff`.
+///
+/// # Panics
+///
+/// Will panic if the length of `dst` is not 4 times the length of `src`.
+#[inline]
+pub fn l8_to_bgra(src: &[u8], dst: &mut [u8]) {
+    assert_eq!(dst.len(), src.len() * 4);
+    for (&s, d) in src.iter().zip(dst.chunks_exact_mut(4)) {
+        let d: &mut [u8; 4] = d.try_into().unwrap();
+        d[0] = s;
+        d[1] = s;
+        d[2] = s;
+        d[3] = 0xff;
+    }
+}
+
+/// Copies `src` (treated as 3-byte RGB chunks) into `dst` (treated as 4-byte
+/// BGRA chunks), filling out `dst` by adding a `0xff` alpha value at the end
+/// of each entry. There being no source alpha channel, `alpha_mode` makes no
+/// difference here (premultiplying by `a=0xff` is the identity).
+///
+/// # Panics
+///
+/// Will panic if
+///
+/// * The length of `src` is not a multiple of 3.
+/// * The length of `dst` is not a multiple of 4.
+/// * `src` and `dst` do not have the same length in chunks.
+#[inline]
+pub fn rgb_to_bgra(src: &[u8], dst: &mut [u8], _alpha_mode: AlphaMode) {
+    let nsrc = src.len();
+    let ndst = dst.len();
+    assert_eq!(0, nsrc % 3);
+    assert_eq!(0, ndst % 4);
+    assert_eq!(nsrc, (ndst / 4) * 3);
+    for (s, d) in src.chunks_exact(3).zip(dst.chunks_exact_mut(4)) {
+        let s: &[u8; 3] = s.try_into().unwrap();
+        let d: &mut [u8; 4] = d.try_into().unwrap();
+        d[0] = s[2]; // R
+        d[1] = s[1]; // G
+        d[2] = s[0]; // B
+        d[3] = 0xff; // A
+    }
+}
+
+/// Copies `src` (treated as 4-byte RGBA chunks) into `dst` (treated as
+/// 4-byte BGRA chunks), applying `alpha_mode`.
+///
+/// # Panics
+///
+/// Will panic if
+///
+/// * The length of `src` is not a multiple of 4.
+/// * The length of `dst` is not a multiple of 4.
+/// * `src` and `dst` do not have the same length.
+#[inline]
+pub fn rgba_to_bgra(src: &[u8], dst: &mut [u8], alpha_mode: AlphaMode) {
+    let nsrc = src.len();
+    let ndst = dst.len();
+    assert_eq!(0, nsrc % 4);
+    assert_eq!(0, ndst % 4);
+    assert_eq!(nsrc, ndst);
+    for (s, d) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+        let s: &[u8; 4] = s.try_into().unwrap();
+        let d: &mut [u8; 4] = d.try_into().unwrap();
+        let (r, g, b, a) = (s[0], s[1], s[2], s[3]);
+        match alpha_mode {
+            AlphaMode::Straight => {
+                d[0] = b;
+                d[1] = g;
+                d[2] = r;
+                d[3] = a;
+            }
+            AlphaMode::Premultiplied => {
+                d[0] = premultiply(b, a);
+                d[1] = premultiply(g, a);
+                d[2] = premultiply(r, a);
+                d[3] = a;
+            }
+        }
+    }
+}
+
+#[inline]
+fn premultiply(channel: u8, alpha: u8) -> u8 {
+    (((channel as u32) * (alpha as u32) + 127) / 255) as u8
+}
@@ -0,0 +1,265 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// A self-contained modified-median-cut color quantizer plus nearest-color
+// remapping and optional Floyd-Steinberg dithering, used by encode::gif and
+// encode::png to turn a raw BGRA frame into indexed pixels + a palette, the
+// way the Rust pipeline behind tools like gifski does before handing off to
+// an LZW or Deflate encoder.
+
+use std::collections::HashMap;
+
+/// An RGB color: one distinct color of the histogram.
+#[derive(Clone, Copy, Debug, Default)]
+struct Rgb {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+struct Bucket {
+    color: Rgb,
+    weight: u32,
+}
+
+/// One box in the median-cut tree: a set of histogram buckets plus the
+/// tightest axis-aligned RGB bounding box around them.
+struct Cut {
+    buckets: Vec<Bucket>,
+    r_range: (u8, u8),
+    g_range: (u8, u8),
+    b_range: (u8, u8),
+}
+
+impl Cut {
+    fn new(buckets: Vec<Bucket>) -> Cut {
+        let (mut r0, mut g0, mut b0) = (255u8, 255u8, 255u8);
+        let (mut r1, mut g1, mut b1) = (0u8, 0u8, 0u8);
+        for b in &buckets {
+            r0 = r0.min(b.color.r);
+            g0 = g0.min(b.color.g);
+            b0 = b0.min(b.color.b);
+            r1 = r1.max(b.color.r);
+            g1 = g1.max(b.color.g);
+            b1 = b1.max(b.color.b);
+        }
+        Cut {
+            buckets,
+            r_range: (r0, r1),
+            g_range: (g0, g1),
+            b_range: (b0, b1),
+        }
+    }
+
+    fn total_weight(&self) -> u64 {
+        self.buckets.iter().map(|b| b.weight as u64).sum()
+    }
+
+    /// The weighted variance of this box, summed over R/G/B. The box with
+    /// the largest variance is the one most worth splitting next.
+    fn weighted_variance(&self) -> f64 {
+        let total = self.total_weight() as f64;
+        if total == 0.0 {
+            return 0.0;
+        }
+        let (mut sr, mut sg, mut sb) = (0f64, 0f64, 0f64);
+        for b in &self.buckets {
+            let w = b.weight as f64;
+            sr += w * b.color.r as f64;
+            sg += w * b.color.g as f64;
+            sb += w * b.color.b as f64;
+        }
+        let (mr, mg, mb) = (sr / total, sg / total, sb / total);
+        let mut variance = 0f64;
+        for b in &self.buckets {
+            let w = b.weight as f64;
+            variance += w * (b.color.r as f64 - mr).powi(2);
+            variance += w * (b.color.g as f64 - mg).powi(2);
+            variance += w * (b.color.b as f64 - mb).powi(2);
+        }
+        variance
+    }
+
+    /// Splits this box at the median of its longest axis, returning the two
+    /// child boxes, or giving this box back unchanged (as `Err`) if it
+    /// contains a single distinct color.
+    fn split(mut self) -> Result<(Cut, Cut), Cut> {
+        if self.buckets.len() < 2 {
+            return Err(self);
+        }
+        let r_span = self.r_range.1 - self.r_range.0;
+        let g_span = self.g_range.1 - self.g_range.0;
+        let b_span = self.b_range.1 - self.b_range.0;
+        if (r_span == 0) && (g_span == 0) && (b_span == 0) {
+            return Err(self);
+        }
+        if (r_span >= g_span) && (r_span >= b_span) {
+            self.buckets.sort_by_key(|b| b.color.r);
+        } else if g_span >= b_span {
+            self.buckets.sort_by_key(|b| b.color.g);
+        } else {
+            self.buckets.sort_by_key(|b| b.color.b);
+        }
+
+        let total = self.total_weight();
+        let mut cumulative = 0u64;
+        let mut split_at = self.buckets.len() / 2;
+        for (i, b) in self.buckets.iter().enumerate() {
+            cumulative += b.weight as u64;
+            if cumulative * 2 >= total {
+                split_at = (i + 1).min(self.buckets.len() - 1).max(1);
+                break;
+            }
+        }
+
+        let rest = self.buckets.split_off(split_at);
+        Ok((Cut::new(self.buckets), Cut::new(rest)))
+    }
+
+    /// The weight-averaged color of this box: the palette entry it becomes.
+    fn average(&self) -> [u8; 3] {
+        let total = self.total_weight().max(1) as f64;
+        let (mut sr, mut sg, mut sb) = (0f64, 0f64, 0f64);
+        for b in &self.buckets {
+            let w = b.weight as f64;
+            sr += w * b.color.r as f64;
+            sg += w * b.color.g as f64;
+            sb += w * b.color.b as f64;
+        }
+        [
+            (sr / total).round() as u8,
+            (sg / total).round() as u8,
+            (sb / total).round() as u8,
+        ]
+    }
+}
+
+/// Builds a palette of at most `max_colors` entries for `bgra` (BGRA bytes,
+/// 4 per pixel), by repeatedly popping the box with the largest weighted
+/// variance and splitting it at the median along its longest axis.
+pub fn build_palette(bgra: &[u8], max_colors: usize) -> Vec<[u8; 3]> {
+    let mut histogram: HashMap<(u8, u8, u8), u32> = HashMap::new();
+    for px in bgra.chunks_exact(4) {
+        *histogram.entry((px[2], px[1], px[0])).or_insert(0) += 1;
+    }
+
+    let buckets: Vec<Bucket> = histogram
+        .into_iter()
+        .map(|((r, g, b), weight)| Bucket {
+            color: Rgb { r, g, b },
+            weight,
+        })
+        .collect();
+
+    if buckets.len() <= max_colors {
+        return buckets.iter().map(|b| [b.color.r, b.color.g, b.color.b]).collect();
+    }
+
+    let mut cuts = vec![Cut::new(buckets)];
+    while cuts.len() < max_colors {
+        let splittable = (0..cuts.len())
+            .filter(|&i| cuts[i].buckets.len() >= 2)
+            .max_by(|&a, &b| {
+                cuts[a]
+                    .weighted_variance()
+                    .partial_cmp(&cuts[b].weighted_variance())
+                    .unwrap()
+            });
+        let i = match splittable {
+            Some(i) => i,
+            None => break,
+        };
+        let cut = cuts.swap_remove(i);
+        match cut.split() {
+            Ok((a, b)) => {
+                cuts.push(a);
+                cuts.push(b);
+            }
+            Err(cut) => cuts.push(cut), // Can't split further (one distinct color).
+        }
+    }
+
+    cuts.iter().map(Cut::average).collect()
+}
+
+/// Finds the palette entry closest to `rgb` by squared RGB distance.
+fn nearest(palette: &[[u8; 3]], rgb: [i32; 3]) -> u8 {
+    let mut best_index = 0usize;
+    let mut best_distance = i32::MAX;
+    for (i, p) in palette.iter().enumerate() {
+        let dr = rgb[0] - p[0] as i32;
+        let dg = rgb[1] - p[1] as i32;
+        let db = rgb[2] - p[2] as i32;
+        let distance = dr * dr + dg * dg + db * db;
+        if distance < best_distance {
+            best_distance = distance;
+            best_index = i;
+        }
+    }
+    best_index as u8
+}
+
+/// Remaps `bgra` to palette indices, one byte per pixel. With `dither`,
+/// applies Floyd-Steinberg error diffusion (7/16 right, 3/16 below-left,
+/// 5/16 below, 1/16 below-right) so quantization error doesn't just round
+/// off but spreads into neighboring pixels.
+pub fn remap(bgra: &[u8], width: usize, height: usize, palette: &[[u8; 3]], dither: bool) -> Vec<u8> {
+    let mut indices = vec![0u8; width * height];
+    if !dither {
+        for (i, px) in bgra.chunks_exact(4).enumerate() {
+            indices[i] = nearest(palette, [px[2] as i32, px[1] as i32, px[0] as i32]);
+        }
+        return indices;
+    }
+
+    // Working RGB buffer that error diffusion perturbs in place.
+    let mut rgb: Vec<[i32; 3]> = bgra
+        .chunks_exact(4)
+        .map(|px| [px[2] as i32, px[1] as i32, px[0] as i32])
+        .collect();
+
+    for y in 0..height {
+        for x in 0..width {
+            let i = y * width + x;
+            let wanted = rgb[i];
+            let index = nearest(palette, wanted);
+            indices[i] = index;
+
+            let chosen = palette[index as usize];
+            let error = [
+                wanted[0] - chosen[0] as i32,
+                wanted[1] - chosen[1] as i32,
+                wanted[2] - chosen[2] as i32,
+            ];
+            let mut diffuse = |dx: isize, dy: usize, num: i32| {
+                let nx = x as isize + dx;
+                let ny = y + dy;
+                if (nx < 0) || (nx as usize >= width) || (ny >= height) {
+                    return;
+                }
+                let j = ny * width + nx as usize;
+                for c in 0..3 {
+                    rgb[j][c] += error[c] * num / 16;
+                }
+            };
+            diffuse(1, 0, 7);
+            diffuse(-1, 1, 3);
+            diffuse(0, 1, 5);
+            diffuse(1, 1, 1);
+        }
+    }
+    indices
+}
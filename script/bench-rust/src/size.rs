@@ -0,0 +1,174 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// `--mode=size` builds a minimal, single-decode entry point per library
+// (under ../size_bins, one [[bin]] target per library in Cargo.toml) and
+// reports its stripped native `.text` size alongside its gzipped
+// `wasm32-unknown-unknown` size. A decoder's throughput (ns/op, MB/s) is
+// only half the story for embedded and WASM targets: minipng, for example,
+// advertises its `.wasm.gz` size relative to the `png` crate, not just
+// speed. This mode reuses the same benchstat-compatible line format as the
+// decode/encode modes so all three can be fed to the same `benchstat` run.
+
+use std::io::Write;
+use std::process::Command;
+
+/// One (library name, size_bins/*.rs file stem) pair. The file stem doubles
+/// as the `[[bin]] name = "..."` Cargo.toml entry that builds it.
+struct Entry {
+    library: &'static str,
+    bin: &'static str,
+}
+
+const ENTRIES: &[Entry] = &[
+    Entry {
+        library: "flate2",
+        bin: "flate2",
+    },
+    Entry {
+        library: "image-gif",
+        bin: "image_gif",
+    },
+    Entry {
+        library: "gif.rs",
+        bin: "gif_dot_rs",
+    },
+    Entry {
+        library: "jpeg-decoder",
+        bin: "jpeg_decoder",
+    },
+    Entry {
+        library: "zune-jpeg",
+        bin: "zune_jpeg",
+    },
+];
+
+pub fn run(args: &crate::cli::Args) {
+    for entry in ENTRIES {
+        if !crate::cli::matches_focus(&args.focus, entry.library) {
+            continue;
+        }
+        match measure(entry.bin) {
+            Ok((text_bytes, wasm_gz_bytes)) => {
+                // Two separate lines, each with its own iterations column
+                // (always 1, since this is a single build-and-measure, not
+                // a timing loop), so benchstat parses `text_bytes` and
+                // `wasm_gz_bytes` as two distinct value/unit pairs instead
+                // of misreading one of them as the iterations count.
+                print!(
+                    "Benchmarkrust_size_text_{:16}       1   {:12} text_bytes/op\n",
+                    entry.library, text_bytes
+                );
+                print!(
+                    "Benchmarkrust_size_wasm_gz_{:16}    1   {:12} wasm_gz_bytes/op\n",
+                    entry.library, wasm_gz_bytes
+                );
+            }
+            Err(message) => {
+                eprint!("{}: {}\n", entry.library, message);
+            }
+        }
+    }
+}
+
+/// Builds `size_bins/<bin>.rs` natively and for wasm32-unknown-unknown,
+/// returning (stripped native .text bytes, gzipped wasm bytes).
+fn measure(bin: &str) -> Result<(u64, u64), String> {
+    let native_path = build(bin, None)?;
+    let text_bytes = native_text_size(&native_path)?;
+
+    let wasm_path = build(bin, Some("wasm32-unknown-unknown"))?;
+    let wasm_gz_bytes = gzipped_size(&wasm_path)?;
+
+    Ok((text_bytes, wasm_gz_bytes))
+}
+
+/// Runs `cargo build --release [--target TARGET] --bin BIN` and returns the
+/// path to the resulting artifact.
+fn build(bin: &str, target: Option<&str>) -> Result<std::path::PathBuf, String> {
+    let mut cmd = Command::new("cargo");
+    cmd.arg("build").arg("--release").arg("--bin").arg(bin);
+    let mut out_dir = std::path::PathBuf::from("target");
+    if let Some(target) = target {
+        cmd.arg("--target").arg(target);
+        out_dir.push(target);
+    }
+    out_dir.push("release");
+
+    let status = cmd
+        .status()
+        .map_err(|e| format!("couldn't run cargo build: {}", e))?;
+    if !status.success() {
+        return Err(format!("cargo build --bin {} failed", bin));
+    }
+
+    let file_name = match target {
+        Some(_) => format!("{}.wasm", bin),
+        None => bin.to_string(),
+    };
+    Ok(out_dir.join(file_name))
+}
+
+/// Strips `path` and returns the size (in bytes) of its `.text` section, by
+/// shelling out to the standard `strip` and `size` binutils.
+fn native_text_size(path: &std::path::Path) -> Result<u64, String> {
+    let stripped = path.with_extension("stripped");
+    std::fs::copy(path, &stripped).map_err(|e| format!("couldn't copy binary: {}", e))?;
+
+    let status = Command::new("strip")
+        .arg(&stripped)
+        .status()
+        .map_err(|e| format!("couldn't run strip: {}", e))?;
+    if !status.success() {
+        return Err("strip failed".to_string());
+    }
+
+    let output = Command::new("size")
+        .arg(&stripped)
+        .output()
+        .map_err(|e| format!("couldn't run size: {}", e))?;
+    if !output.status.success() {
+        return Err("size failed".to_string());
+    }
+
+    // Berkeley format: a header line, then "text  data  bss  dec  hex  filename".
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let fields_line = stdout
+        .lines()
+        .nth(1)
+        .ok_or_else(|| "unexpected `size` output".to_string())?;
+    let text_field = fields_line
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| "unexpected `size` output".to_string())?;
+    text_field
+        .parse::<u64>()
+        .map_err(|e| format!("couldn't parse `.text` size: {}", e))
+}
+
+/// gzips `path` at the default compression level and returns the resulting
+/// byte count, without writing the compressed bytes anywhere.
+fn gzipped_size(path: &std::path::Path) -> Result<u64, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("couldn't read wasm binary: {}", e))?;
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder
+        .write_all(&bytes)
+        .map_err(|e| format!("couldn't gzip wasm binary: {}", e))?;
+    let compressed = encoder
+        .finish()
+        .map_err(|e| format!("couldn't gzip wasm binary: {}", e))?;
+    Ok(compressed.len() as u64)
+}
@@ -0,0 +1,38 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// Small, dependency-free statistics helpers for `--sample` mode: a single
+// aggregate ns/op number hides scheduling noise and can't be fed into
+// benchstat's A/B significance testing. Median and median-absolute-deviation
+// (rather than mean and standard deviation) are used because they're robust
+// to the occasional outlier rep (e.g. a GC pause or a context switch) without
+// needing to discard samples explicitly.
+
+/// Returns the median of `values`, sorting `values` in place. Panics if
+/// `values` is empty.
+pub fn median(values: &mut [u64]) -> u64 {
+    values.sort_unstable();
+    values[values.len() / 2]
+}
+
+/// Returns the median absolute deviation of `values` around `med`, a
+/// robust-to-outliers alternative to standard deviation. `med` is expected
+/// to be `median(values)`, but isn't recomputed here so callers can reuse
+/// the value they already printed.
+pub fn median_absolute_deviation(values: &[u64], med: u64) -> u64 {
+    let mut deviations: Vec<u64> = values.iter().map(|&v| v.abs_diff(med)).collect();
+    median(&mut deviations)
+}
@@ -0,0 +1,72 @@
+// Copyright 2024 The Wuffs Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// ----------------
+
+// `--verify` cross-checks that a mimic library didn't just produce the
+// right *number* of decoded bytes (which `want_num_bytes` already checks on
+// every iteration) but the right *content*. Without this, a mimic library
+// that decoded visually wrong pixels at the correct size would still pass.
+//
+// Each corpus entry may carry a `Golden` value: an FNV-1a hash of the whole
+// decoded buffer, plus the first and last decoded bytes (the old
+// bench-rust-gif-dot-rs program hard-coded exactly this kind of top-left /
+// bottom-right sanity check as `FIRST_PIXEL`/`LAST_PIXEL`; this generalizes
+// it to every corpus entry and library).
+//
+// Coverage today is sparse (see corpus.rs's CorpusEntry.golden doc comment)
+// rather than "every corpus entry": only one GIF entry has a golden so far.
+// `--verify` reports entries it can't check instead of silently passing
+// them, so sparse coverage is visible rather than looking complete.
+
+/// A golden value that a decoded buffer is checked against.
+pub struct Golden {
+    pub fnv1a_hash: u32,
+    pub first_byte: u8,
+    pub last_byte: u8,
+}
+
+/// The 32-bit FNV-1a hash (http://www.isthe.com/chongo/tech/comp/fnv/) of
+/// `data`. Chosen over CRC-32 for being branch-free and a few lines of code,
+/// not for any cryptographic property: it only needs to be stable across
+/// runs, not collision-resistant against an adversary.
+pub fn fnv1a(data: &[u8]) -> u32 {
+    const OFFSET_BASIS: u32 = 0x811c_9dc5;
+    const PRIME: u32 = 0x0100_0193;
+    let mut hash = OFFSET_BASIS;
+    for &byte in data {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
+/// Panics if `decoded` doesn't match `golden`.
+pub fn check(format: &str, library: &str, entry: &str, decoded: &[u8], golden: &Golden) {
+    let first_byte = decoded[0];
+    let last_byte = decoded[decoded.len() - 1];
+    if (first_byte != golden.first_byte) || (last_byte != golden.last_byte) {
+        panic!(
+            "{}/{}/{}: corner bytes: got ({:#04x}, {:#04x}), want ({:#04x}, {:#04x})",
+            format, library, entry, first_byte, last_byte, golden.first_byte, golden.last_byte
+        );
+    }
+    let hash = fnv1a(decoded);
+    if hash != golden.fnv1a_hash {
+        panic!(
+            "{}/{}/{}: fnv1a hash: got {:#010x}, want {:#010x}",
+            format, library, entry, hash, golden.fnv1a_hash
+        );
+    }
+}